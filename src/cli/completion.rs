@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use clap::{CommandFactory, Parser};
+use clap_complete::{generate, Shell};
+use clap_complete_nushell::Nushell;
+use miette::IntoDiagnostic;
+
+use crate::cli::Cli;
+
+/// The shells we can generate completion scripts for.
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+    Nushell,
+}
+
+/// Generate a shell completion script for the `pixi` CLI and print it to stdout.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The shell to generate completions for.
+    #[arg(long, value_enum)]
+    pub shell: CompletionShell,
+}
+
+pub fn execute(args: Args) -> miette::Result<()> {
+    let mut command = Cli::command();
+    let bin_name = command.get_name().to_string();
+    let mut stdout = io::stdout();
+
+    match args.shell {
+        CompletionShell::Bash => generate(Shell::Bash, &mut command, bin_name, &mut stdout),
+        CompletionShell::Zsh => generate(Shell::Zsh, &mut command, bin_name, &mut stdout),
+        CompletionShell::Fish => generate(Shell::Fish, &mut command, bin_name, &mut stdout),
+        CompletionShell::PowerShell => {
+            generate(Shell::PowerShell, &mut command, bin_name, &mut stdout)
+        }
+        CompletionShell::Nushell => generate(Nushell, &mut command, bin_name, &mut stdout),
+    };
+
+    stdout.flush().into_diagnostic()
+}
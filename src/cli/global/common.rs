@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use indexmap::IndexMap;
 use miette::IntoDiagnostic;
+use rattler::install::Installer;
 use rattler_conda_types::{
     Channel, ChannelConfig, MatchSpec, PackageName, Platform, PrefixRecord, RepoDataRecord,
 };
@@ -150,19 +151,20 @@ pub fn load_package_records(
     let available_packages =
         SparseRepoData::load_records_recursive(sparse_repodata.values(), vec![package_name], None)
             .into_diagnostic()?;
-    let virtual_packages = rattler_virtual_packages::VirtualPackage::current()
-        .into_diagnostic()?
-        .iter()
-        .cloned()
-        .map(Into::into)
-        .collect();
+    let virtual_packages: Vec<rattler_conda_types::GenericVirtualPackage> =
+        rattler_virtual_packages::VirtualPackage::current()
+            .into_diagnostic()?
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
 
     // Solve for environment
     // Construct a solver task that we can start solving.
     let task = SolverTask {
         specs: vec![package_matchspec],
         available_packages: &available_packages,
-        virtual_packages,
+        virtual_packages: virtual_packages.clone(),
         locked_packages: vec![],
         pinned_packages: vec![],
         timeout: None,
@@ -171,6 +173,13 @@ pub fn load_package_records(
     // Solve it
     let records = resolvo::Solver.solve(task).into_diagnostic()?;
 
+    // Make sure the solver didn't hand us back a set of records that's only
+    // consistent in isolation from each other. The solver also had the current
+    // platform's virtual packages available, so a `depends` entry on e.g.
+    // `__unix` is expected to be satisfied by those rather than by `records`.
+    super::validate::validate_package_records(&records, Platform::current(), &virtual_packages)
+        .into_diagnostic()?;
+
     Ok(records)
 }
 
@@ -228,3 +237,44 @@ pub async fn find_designated_package(
         .find(|r| r.repodata_record.package_record.name == *package_name)
         .ok_or_else(|| miette::miette!("could not find {} in prefix", package_name.as_source()))
 }
+
+/// Link the solved `records` into `prefix`, replacing whatever is currently installed there.
+///
+/// # Returns
+///
+/// The resulting [`PrefixRecord`]s, one per linked package
+pub(super) async fn install_prefix(
+    prefix: &Prefix,
+    records: &[RepoDataRecord],
+    authenticated_client: ClientWithMiddleware,
+) -> miette::Result<Vec<PrefixRecord>> {
+    Installer::new()
+        .with_target_platform(Platform::current())
+        .with_download_client(authenticated_client)
+        .install(prefix.root(), records.to_vec())
+        .await
+        .into_diagnostic()
+}
+
+/// Expose the executables of the given `prefix_record` by symlinking them into [`BinDir`].
+pub(super) async fn expose_executables(
+    bin_dir: &PathBuf,
+    prefix: &Prefix,
+    prefix_record: &PrefixRecord,
+) -> miette::Result<()> {
+    for executable in prefix.find_executables(std::slice::from_ref(prefix_record)) {
+        let link_path = bin_dir.join(executable.file_name().unwrap_or_default());
+        if tokio::fs::try_exists(&link_path).await.into_diagnostic()? {
+            tokio::fs::remove_file(&link_path).await.into_diagnostic()?;
+        }
+        #[cfg(unix)]
+        tokio::fs::symlink(&executable, &link_path)
+            .await
+            .into_diagnostic()?;
+        #[cfg(windows)]
+        tokio::fs::symlink_file(&executable, &link_path)
+            .await
+            .into_diagnostic()?;
+    }
+    Ok(())
+}
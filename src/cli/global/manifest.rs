@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use miette::IntoDiagnostic;
+use rattler_conda_types::{MatchSpec, NamelessMatchSpec, PackageName};
+use serde::{Deserialize, Serialize};
+
+use super::common::home_path;
+
+/// Name of the global manifest file, relative to the pixi home directory.
+pub const GLOBAL_MANIFEST_FILE_NAME: &str = "global.toml";
+
+/// A single tool the user wants available globally.
+///
+/// The key under which this entry is stored in the manifest's `[envs]` table
+/// is used as the package name if `spec` does not already pin one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalManifestEntry {
+    /// The match spec used to solve and install the package, e.g. `python>=3.11`,
+    /// or just `>=3.11` to take the package name from the entry's key.
+    pub spec: String,
+
+    /// Channels to solve this entry against. Defaults to `conda-forge` when empty.
+    #[serde(default)]
+    pub channels: Vec<String>,
+
+    /// Optional smoke test to run against the installed environment before the
+    /// install is considered successful.
+    #[serde(default)]
+    pub test: Option<GlobalManifestEntryTest>,
+}
+
+/// A smoke test to run inside a freshly installed global environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalManifestEntryTest {
+    /// Shell commands to run inside the installed prefix; each must exit 0.
+    #[serde(default)]
+    pub commands: Vec<String>,
+
+    /// Python module names that must be importable in the installed prefix.
+    #[serde(default)]
+    pub imports: Vec<String>,
+}
+
+impl GlobalManifestEntry {
+    /// Parse the stored spec string into a [`MatchSpec`], using `key` (the name this
+    /// entry is stored under in the manifest's `[envs]` table) as the package name
+    /// when `spec` does not already pin one.
+    ///
+    /// Returns an error if `spec` pins a different package name than `key`.
+    pub fn match_spec(&self, key: &str) -> miette::Result<MatchSpec> {
+        // A `MatchSpec` must start with a package name, so a spec without one
+        // (e.g. `>=3.11`) is parsed as a nameless spec instead.
+        let mut match_spec: MatchSpec = match self.spec.parse() {
+            Ok(match_spec) => match_spec,
+            Err(_) => MatchSpec::from_nameless(
+                self.spec.parse::<NamelessMatchSpec>().into_diagnostic()?,
+                None,
+            ),
+        };
+        match &match_spec.name {
+            None => {
+                match_spec.name = Some(PackageName::try_from(key).into_diagnostic()?);
+            }
+            Some(name) if name.as_source() != key => {
+                miette::bail!(
+                    "global manifest entry '{key}' has spec '{}', which names package '{}' instead of '{key}'",
+                    self.spec,
+                    name.as_source()
+                );
+            }
+            Some(_) => {}
+        }
+        Ok(match_spec)
+    }
+}
+
+/// Declarative description of the desired global environments, stored at
+/// `~/.pixi/global.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalManifest {
+    /// Map from package name to the desired install entry.
+    #[serde(default, rename = "envs")]
+    pub envs: HashMap<String, GlobalManifestEntry>,
+}
+
+impl GlobalManifest {
+    /// Path to the global manifest file, default to `$HOME/.pixi/global.toml`.
+    pub fn path() -> miette::Result<PathBuf> {
+        home_path().map(|path| path.join(GLOBAL_MANIFEST_FILE_NAME))
+    }
+
+    /// Load the global manifest from disk, returning an empty manifest if it
+    /// does not exist yet.
+    pub async fn from_default_path() -> miette::Result<Self> {
+        let path = Self::path()?;
+        if !tokio::fs::try_exists(&path).await.into_diagnostic()? {
+            return Ok(Self::default());
+        }
+        let contents = tokio::fs::read_to_string(&path).await.into_diagnostic()?;
+        toml_edit::de::from_str(&contents).into_diagnostic()
+    }
+
+    /// The package names this manifest wants installed globally.
+    pub fn package_names(&self) -> miette::Result<Vec<PackageName>> {
+        self.envs
+            .keys()
+            .map(|name| {
+                PackageName::try_from(name.as_str())
+                    .into_diagnostic()
+                    .map_err(|e| {
+                        e.wrap_err(format!(
+                            "invalid package name '{name}' in {GLOBAL_MANIFEST_FILE_NAME}"
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_manifest_toml() {
+        let manifest: GlobalManifest = toml_edit::de::from_str(
+            r#"
+            [envs.ripgrep]
+            spec = "ripgrep>=14"
+            channels = ["conda-forge"]
+
+            [envs.black]
+            spec = "black"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.envs.len(), 2);
+        assert_eq!(manifest.envs["ripgrep"].spec, "ripgrep>=14");
+        assert_eq!(manifest.envs["ripgrep"].channels, vec!["conda-forge"]);
+        assert!(manifest.envs["black"].channels.is_empty());
+    }
+
+    #[test]
+    fn match_spec_falls_back_to_key_when_spec_has_no_name() {
+        let entry = GlobalManifestEntry {
+            spec: ">=3.11".to_string(),
+            channels: Vec::new(),
+            test: None,
+        };
+
+        let match_spec = entry.match_spec("python").unwrap();
+        assert_eq!(
+            match_spec.name.as_ref().map(PackageName::as_source),
+            Some("python")
+        );
+    }
+
+    #[test]
+    fn match_spec_accepts_spec_naming_the_same_package_as_key() {
+        let entry = GlobalManifestEntry {
+            spec: "python>=3.11".to_string(),
+            channels: Vec::new(),
+            test: None,
+        };
+
+        let match_spec = entry.match_spec("python").unwrap();
+        assert_eq!(
+            match_spec.name.as_ref().map(PackageName::as_source),
+            Some("python")
+        );
+    }
+
+    #[test]
+    fn match_spec_rejects_spec_naming_a_different_package_than_key() {
+        let entry = GlobalManifestEntry {
+            spec: "python>=3.11".to_string(),
+            channels: Vec::new(),
+            test: None,
+        };
+
+        assert!(entry.match_spec("ripgrep").is_err());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod common;
+pub mod manifest;
+pub mod sync;
+mod test;
+pub mod validate;
+
+pub use sync::{execute as sync, Args as SyncArgs};
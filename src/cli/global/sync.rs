@@ -0,0 +1,183 @@
+use clap::Parser;
+use miette::IntoDiagnostic;
+use rattler_conda_types::{Channel, ChannelConfig, MatchSpec, PackageName, RepoDataRecord};
+use reqwest_middleware::ClientWithMiddleware;
+
+use crate::prefix::Prefix;
+
+use super::common::{
+    bin_env_dir, expose_executables, find_designated_package, get_client_and_sparse_repodata,
+    install_prefix, load_package_records, BinDir, BinEnvDir,
+};
+use super::manifest::{GlobalManifest, GlobalManifestEntryTest};
+use super::test::run_post_install_test;
+
+/// Reconcile the globally installed tools with the `~/.pixi/global.toml` manifest.
+///
+/// Installs anything listed in the manifest but missing, upgrades environments
+/// whose installed version no longer satisfies the manifest's spec, and removes
+/// environments (and their `~/.pixi/bin` symlinks) that are no longer listed.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Only report what would change, without installing or removing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+pub async fn execute(args: Args) -> miette::Result<()> {
+    let manifest = GlobalManifest::from_default_path().await?;
+    let channel_config = ChannelConfig::default();
+
+    let wanted = manifest.package_names()?;
+    let installed = installed_package_names().await?;
+
+    let to_remove = installed
+        .iter()
+        .filter(|name| !wanted.contains(name))
+        .cloned()
+        .collect::<Vec<_>>();
+    let to_sync = wanted.iter().cloned().collect::<Vec<_>>();
+
+    for package_name in &to_remove {
+        eprintln!("Removing {}", package_name.as_source());
+        if !args.dry_run {
+            remove_environment(package_name).await?;
+        }
+    }
+
+    for package_name in &to_sync {
+        let entry = manifest
+            .envs
+            .get(package_name.as_source())
+            .expect("package name was derived from the manifest's own keys");
+        let match_spec = entry.match_spec(package_name.as_source())?;
+        let channels = entry_channels(entry, &channel_config)?;
+
+        let already_installed = installed.contains(package_name);
+        if already_installed && !needs_upgrade(package_name, &match_spec).await? {
+            continue;
+        }
+
+        eprintln!(
+            "{} {}",
+            if already_installed {
+                "Upgrading"
+            } else {
+                "Installing"
+            },
+            package_name.as_source()
+        );
+        if !args.dry_run {
+            let (client, sparse_repodata) = get_client_and_sparse_repodata(&channels).await?;
+            let records = load_package_records(match_spec, &sparse_repodata)?;
+            install_environment(package_name, records, &client, entry.test.as_ref()).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the channels listed on a manifest entry, falling back to `conda-forge`.
+fn entry_channels(
+    entry: &super::manifest::GlobalManifestEntry,
+    channel_config: &ChannelConfig,
+) -> miette::Result<Vec<Channel>> {
+    if entry.channels.is_empty() {
+        return Ok(vec![Channel::from_str("conda-forge", channel_config)
+            .into_diagnostic()?]);
+    }
+    entry
+        .channels
+        .iter()
+        .map(|c| Channel::from_str(c, channel_config).into_diagnostic())
+        .collect()
+}
+
+/// Enumerate the package names with an existing directory in [`bin_env_dir`].
+async fn installed_package_names() -> miette::Result<Vec<PackageName>> {
+    let envs_dir = bin_env_dir()?;
+    if !tokio::fs::try_exists(&envs_dir).await.into_diagnostic()? {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = tokio::fs::read_dir(&envs_dir).await.into_diagnostic()?;
+    let mut names = Vec::new();
+    while let Some(entry) = entries.next_entry().await.into_diagnostic()? {
+        if !entry.file_type().await.into_diagnostic()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if let Ok(package_name) = PackageName::try_from(name) {
+                names.push(package_name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Whether the currently installed environment for `package_name` no longer
+/// satisfies `match_spec`, and therefore needs to be reinstalled.
+async fn needs_upgrade(package_name: &PackageName, match_spec: &MatchSpec) -> miette::Result<bool> {
+    let BinEnvDir(bin_prefix) = BinEnvDir::from_existing(package_name).await?;
+    let prefix = Prefix::new(bin_prefix);
+    let installed = find_designated_package(&prefix, package_name).await?;
+    Ok(!match_spec.matches(&installed.repodata_record.package_record))
+}
+
+/// Remove the environment directory for `package_name` and the `~/.pixi/bin`
+/// symlinks that point into it.
+async fn remove_environment(package_name: &PackageName) -> miette::Result<()> {
+    let BinEnvDir(bin_prefix) = BinEnvDir::from_existing(package_name).await?;
+
+    let BinDir(bin_dir) = BinDir::from_existing().await?;
+    let mut entries = tokio::fs::read_dir(&bin_dir).await.into_diagnostic()?;
+    while let Some(entry) = entries.next_entry().await.into_diagnostic()? {
+        let path = entry.path();
+        if tokio::fs::read_link(&path)
+            .await
+            .map(|target| target.starts_with(&bin_prefix))
+            .unwrap_or(false)
+        {
+            tokio::fs::remove_file(&path).await.into_diagnostic()?;
+        }
+    }
+
+    tokio::fs::remove_dir_all(&bin_prefix)
+        .await
+        .into_diagnostic()
+}
+
+/// Install the solved `records` into the environment for `package_name` (created
+/// if missing, replaced in place when upgrading), expose its executables under
+/// `~/.pixi/bin`, and, if `test` is set, run the manifest's smoke test before
+/// accepting the install.
+///
+/// If the smoke test fails, the environment is removed together with its
+/// `~/.pixi/bin` symlinks (on an upgrade the previously installed version is not
+/// restored) and the failure is returned to the caller.
+async fn install_environment(
+    package_name: &PackageName,
+    records: Vec<RepoDataRecord>,
+    client: &ClientWithMiddleware,
+    test: Option<&GlobalManifestEntryTest>,
+) -> miette::Result<()> {
+    let BinEnvDir(bin_prefix) = BinEnvDir::create(package_name).await?;
+    let prefix = Prefix::new(bin_prefix);
+    install_prefix(&prefix, &records, client.clone()).await?;
+
+    let BinDir(bin_dir) = BinDir::create().await?;
+    let installed = find_designated_package(&prefix, package_name).await?;
+    expose_executables(&bin_dir, &prefix, &installed).await?;
+
+    if let Some(test) = test {
+        if let Err(err) = run_post_install_test(&prefix, test).await {
+            remove_environment(package_name).await?;
+            return Err(miette::miette!(
+                "post-install test failed for {}, install was rolled back: {err}",
+                package_name.as_source()
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use miette::IntoDiagnostic;
+
+use crate::prefix::Prefix;
+
+use super::manifest::GlobalManifestEntryTest;
+
+/// Run the post-install smoke test against a freshly installed `prefix`.
+///
+/// Activates the prefix environment, then runs each `commands` entry as a
+/// shell command and imports each `imports` entry with `python -c "import <name>"`.
+/// The first failing step is returned as an error.
+pub(super) async fn run_post_install_test(
+    prefix: &Prefix,
+    test: &GlobalManifestEntryTest,
+) -> miette::Result<()> {
+    let activation_env = prefix.activation_environment_variables().await?;
+    let activation_env = &activation_env;
+    run_test_commands(test, |command| async move {
+        run_in_prefix(prefix, activation_env, &command).await
+    })
+    .await
+}
+
+/// Build the command used to verify `module` is importable.
+fn import_command(module: &str) -> String {
+    format!("python -c \"import {module}\"")
+}
+
+/// Run every `commands` entry, then every `imports` entry (via [`import_command`]),
+/// in that order, stopping at the first failure.
+///
+/// Split out from [`run_post_install_test`] so the ordering and short-circuiting
+/// behavior can be tested without a real [`Prefix`].
+async fn run_test_commands<F, Fut>(test: &GlobalManifestEntryTest, mut run: F) -> miette::Result<()>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = miette::Result<()>>,
+{
+    for command in &test.commands {
+        run(command.clone()).await?;
+    }
+    for module in &test.imports {
+        run(import_command(module)).await?;
+    }
+    Ok(())
+}
+
+async fn run_in_prefix(
+    prefix: &Prefix,
+    activation_env: &std::collections::HashMap<String, String>,
+    command: &str,
+) -> miette::Result<()> {
+    let mut process = tokio::process::Command::new(if cfg!(windows) { "cmd" } else { "sh" });
+    if cfg!(windows) {
+        process.arg("/C").arg(command);
+    } else {
+        process.arg("-c").arg(command);
+    }
+    let status = process
+        .envs(activation_env)
+        .current_dir(prefix.root())
+        .status()
+        .await
+        .into_diagnostic()?;
+
+    if !status.success() {
+        return Err(miette::miette!(
+            "post-install test command failed: `{command}`"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_command_formats_as_python_dash_c() {
+        assert_eq!(
+            import_command("numpy"),
+            "python -c \"import numpy\"".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn commands_run_before_imports_in_order() {
+        let test = GlobalManifestEntryTest {
+            commands: vec!["echo one".to_string(), "echo two".to_string()],
+            imports: vec!["numpy".to_string()],
+        };
+
+        let mut seen = Vec::new();
+        run_test_commands(&test, |command| {
+            seen.push(command.to_string());
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            seen,
+            vec![
+                "echo one".to_string(),
+                "echo two".to_string(),
+                import_command("numpy"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_command_short_circuits_remaining_commands_and_imports() {
+        let test = GlobalManifestEntryTest {
+            commands: vec!["false".to_string(), "echo never".to_string()],
+            imports: vec!["numpy".to_string()],
+        };
+
+        let mut seen = Vec::new();
+        let result = run_test_commands(&test, |command| {
+            seen.push(command.to_string());
+            async move {
+                if command == "false" {
+                    Err(miette::miette!("boom"))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(seen, vec!["false".to_string()]);
+    }
+}
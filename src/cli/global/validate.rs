@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rattler_conda_types::{GenericVirtualPackage, MatchSpec, Platform, RepoDataRecord};
+
+/// A single dependency of a solved record that is not satisfied by any other
+/// record in the same solve.
+#[derive(Debug)]
+pub struct UnmetDependency {
+    /// The package that declares the dependency.
+    pub requiring_package: String,
+    /// The MatchSpec of the unmet dependency, as written in `depends`.
+    pub dependency: String,
+}
+
+/// A solved environment that is internally inconsistent.
+///
+/// Collects every violation found while checking a solved set of
+/// [`RepoDataRecord`]s, rather than failing on the first one.
+#[derive(Debug, Default)]
+pub struct ValidatePackageRecordsError {
+    /// Dependencies that are not satisfied by any record in the solve.
+    pub unmet_dependencies: Vec<UnmetDependency>,
+    /// Package names that appear more than once in the solve.
+    pub duplicate_names: Vec<String>,
+    /// Records whose `subdir` is not compatible with the target platform.
+    pub incompatible_subdirs: Vec<String>,
+}
+
+impl ValidatePackageRecordsError {
+    fn is_empty(&self) -> bool {
+        self.unmet_dependencies.is_empty()
+            && self.duplicate_names.is_empty()
+            && self.incompatible_subdirs.is_empty()
+    }
+}
+
+impl fmt::Display for ValidatePackageRecordsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "solved environment is internally inconsistent:")?;
+        for unmet in &self.unmet_dependencies {
+            writeln!(
+                f,
+                "  - {} requires '{}', which is not satisfied by any solved package",
+                unmet.requiring_package, unmet.dependency
+            )?;
+        }
+        for name in &self.duplicate_names {
+            writeln!(f, "  - package '{name}' appears more than once in the solve")?;
+        }
+        for subdir in &self.incompatible_subdirs {
+            writeln!(f, "  - {subdir}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidatePackageRecordsError {}
+
+/// Whether `spec` is satisfied by the given virtual package, e.g. a dependency on
+/// `__unix` being satisfied by the current platform's `__unix` virtual package.
+fn matches_virtual_package(spec: &MatchSpec, virtual_package: &GenericVirtualPackage) -> bool {
+    let Some(name) = &spec.name else {
+        return false;
+    };
+    if *name != virtual_package.name {
+        return false;
+    }
+    match &spec.version {
+        Some(version_spec) => version_spec.matches(&virtual_package.version),
+        None => true,
+    }
+}
+
+/// Check that a solved set of `records` is internally consistent for `target_platform`:
+/// every dependency is satisfied by some record in the set (or by `virtual_packages`,
+/// which the solver also had available but which never appear in `records`), no
+/// package name appears more than once, and every record's `subdir` is compatible
+/// with the target platform.
+///
+/// # Returns
+///
+/// `Ok(())` if the set is consistent, otherwise a [`ValidatePackageRecordsError`]
+/// collecting every violation found.
+pub fn validate_package_records(
+    records: &[RepoDataRecord],
+    target_platform: Platform,
+    virtual_packages: &[GenericVirtualPackage],
+) -> Result<(), ValidatePackageRecordsError> {
+    let mut error = ValidatePackageRecordsError::default();
+
+    let mut by_name: HashMap<&str, Vec<&RepoDataRecord>> = HashMap::new();
+    for record in records {
+        by_name
+            .entry(record.package_record.name.as_normalized())
+            .or_default()
+            .push(record);
+    }
+
+    for (name, matching) in &by_name {
+        if matching.len() > 1 {
+            error.duplicate_names.push((*name).to_string());
+        }
+    }
+
+    for record in records {
+        for dependency in &record.package_record.depends {
+            let satisfied = match dependency.parse::<rattler_conda_types::MatchSpec>() {
+                Ok(spec) => {
+                    records
+                        .iter()
+                        .any(|candidate| spec.matches(&candidate.package_record))
+                        || virtual_packages
+                            .iter()
+                            .any(|vp| matches_virtual_package(&spec, vp))
+                }
+                // An unparsable dependency string can't be checked; treat it as unmet
+                // rather than silently ignoring it.
+                Err(_) => false,
+            };
+            if !satisfied {
+                error.unmet_dependencies.push(UnmetDependency {
+                    requiring_package: record.package_record.name.as_source().to_string(),
+                    dependency: dependency.clone(),
+                });
+            }
+        }
+
+        let record_platform = Platform::from_str(&record.package_record.subdir).ok();
+        let compatible = record.package_record.subdir == "noarch"
+            || record_platform == Some(target_platform);
+        if !compatible {
+            error.incompatible_subdirs.push(format!(
+                "{} has subdir '{}', which is incompatible with {target_platform}",
+                record.package_record.name.as_source(),
+                record.package_record.subdir
+            ));
+        }
+    }
+
+    if error.is_empty() {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rattler_conda_types::{PackageName, PackageRecord, Version};
+    use url::Url;
+
+    use super::*;
+
+    fn record(name: &str, version: &str, depends: Vec<&str>, subdir: &str) -> RepoDataRecord {
+        let mut package_record = PackageRecord::new(
+            PackageName::try_from(name).unwrap(),
+            version.parse::<Version>().unwrap(),
+            "0".to_string(),
+        );
+        package_record.depends = depends.into_iter().map(ToOwned::to_owned).collect();
+        package_record.subdir = subdir.to_string();
+
+        RepoDataRecord {
+            package_record,
+            file_name: format!("{name}-{version}-0.tar.bz2"),
+            url: Url::parse("https://example.com").unwrap(),
+            channel: "conda-forge".to_string(),
+        }
+    }
+
+    fn virtual_package(name: &str, version: &str) -> GenericVirtualPackage {
+        GenericVirtualPackage {
+            name: PackageName::try_from(name).unwrap(),
+            version: version.parse().unwrap(),
+            build_string: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn consistent_set_is_ok() {
+        let records = vec![
+            record("a", "1.0", vec!["b>=1.0"], "noarch"),
+            record("b", "1.0", vec![], "noarch"),
+        ];
+
+        assert!(validate_package_records(&records, Platform::Linux64, &[]).is_ok());
+    }
+
+    #[test]
+    fn unmet_dependency_is_reported() {
+        let records = vec![record("a", "1.0", vec!["b>=1.0"], "noarch")];
+
+        let err = validate_package_records(&records, Platform::Linux64, &[]).unwrap_err();
+        assert_eq!(err.unmet_dependencies.len(), 1);
+        assert_eq!(err.unmet_dependencies[0].requiring_package, "a");
+    }
+
+    #[test]
+    fn dependency_on_virtual_package_is_satisfied() {
+        let records = vec![record("a", "1.0", vec!["__unix"], "noarch")];
+        let virtual_packages = vec![virtual_package("__unix", "0")];
+
+        assert!(
+            validate_package_records(&records, Platform::Linux64, &virtual_packages).is_ok()
+        );
+    }
+
+    #[test]
+    fn duplicate_package_name_is_reported() {
+        let records = vec![
+            record("a", "1.0", vec![], "noarch"),
+            record("a", "2.0", vec![], "noarch"),
+        ];
+
+        let err = validate_package_records(&records, Platform::Linux64, &[]).unwrap_err();
+        assert_eq!(err.duplicate_names, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn incompatible_subdir_is_reported() {
+        let records = vec![record("a", "1.0", vec![], "osx-64")];
+
+        let err = validate_package_records(&records, Platform::Linux64, &[]).unwrap_err();
+        assert_eq!(err.incompatible_subdirs.len(), 1);
+    }
+}
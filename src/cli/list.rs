@@ -1,19 +1,25 @@
 use std::io;
 use std::io::{stdout, Write};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use clap::Parser;
 use console::Color;
 use human_bytes::human_bytes;
+use indexmap::IndexMap;
 use itertools::Itertools;
-use rattler_conda_types::Platform;
+use miette::IntoDiagnostic;
+use rattler_conda_types::{MatchSpec, NamelessMatchSpec, PackageName, Platform, RepoDataRecord};
 use rattler_lock::Package;
+use rattler_networking::AuthenticationMiddleware;
+use rattler_repodata_gateway::sparse::SparseRepoData;
 use serde::Serialize;
 use uv_distribution::RegistryWheelIndex;
 
 use crate::lock_file::{UpdateLockFileOptions, UvResolutionContext};
 use crate::project::manifest::EnvironmentName;
 use crate::pypi_tags::{get_pypi_tags, is_python_record};
+use crate::repodata;
 use crate::Project;
 
 use crate::consts::PROJECT_MANIFEST;
@@ -64,6 +70,13 @@ pub struct Args {
     /// Don't install the environment for pypi solving, only update the lock-file if it can solve without installing.
     #[arg(long)]
     pub no_install: bool,
+
+    /// Whether to show columns with the latest available version and build of each conda package.
+    ///
+    /// Only conda packages are checked; pypi packages always show blank
+    /// latest-version columns, since that would require querying the PyPI index.
+    #[arg(long)]
+    pub outdated: bool,
 }
 
 #[derive(Serialize)]
@@ -75,6 +88,17 @@ struct PackageToOutput {
     kind: String,
     source: Option<String>,
     is_explicit: bool,
+    latest_version: Option<String>,
+    latest_build: Option<String>,
+}
+
+impl PackageToOutput {
+    /// Whether a newer version than the locked one was found.
+    fn is_outdated(&self) -> bool {
+        self.latest_version
+            .as_ref()
+            .is_some_and(|latest| latest != &self.version)
+    }
 }
 
 /// Get directory size
@@ -154,10 +178,49 @@ pub async fn execute(args: Args) -> miette::Result<()> {
             .into_iter()
             .map(|(name, _)| name.as_source().to_string()),
     );
+    // If requested, fetch the conda repodata for just the packages that are
+    // actually locked (not the entire channel index) so we can look up the
+    // latest available version of each, constrained to the project's own spec.
+    let latest_conda_records = if args.outdated {
+        let locked_conda_names = locked_deps
+            .iter()
+            .filter_map(|d| d.as_conda())
+            .map(|r| r.package_record().name.clone())
+            .unique()
+            .collect_vec();
+        let project_dependency_specs: std::collections::HashMap<PackageName, NamelessMatchSpec> =
+            environment
+                .dependencies(None, Some(platform))
+                .into_specs()
+                .collect();
+        fetch_latest_conda_records(
+            &project,
+            platform,
+            &locked_conda_names,
+            &project_dependency_specs,
+        )
+        .await?
+    } else {
+        IndexMap::new()
+    };
+    if args.outdated && locked_deps.iter().any(|d| d.as_pypi().is_some()) {
+        eprintln!(
+            "{}--outdated only checks conda packages; pypi packages are not checked against the PyPI index.",
+            console::style(console::Emoji("! ", "")).yellow(),
+        );
+    }
+
     // Convert the list of package record to specific output format
     let mut packages_to_output = locked_deps
         .iter()
-        .map(|p| create_package_to_output(p, &project_dependency_names, &mut registry_index))
+        .map(|p| {
+            create_package_to_output(
+                p,
+                &project_dependency_names,
+                &mut registry_index,
+                &latest_conda_records,
+            )
+        })
         .collect::<Vec<PackageToOutput>>();
 
     // Filter packages by regex if needed
@@ -197,17 +260,83 @@ pub async fn execute(args: Args) -> miette::Result<()> {
         json_packages(&packages_to_output, args.json_pretty);
     } else {
         // print packages as table
-        print_packages_as_table(&packages_to_output).expect("an io error occurred");
+        print_packages_as_table(&packages_to_output, args.outdated).expect("an io error occurred");
     }
 
     Ok(())
 }
 
-fn print_packages_as_table(packages: &Vec<PackageToOutput>) -> io::Result<()> {
+/// Fetch sparse repodata for the project's channels and `platform`, restricted to
+/// `locked_names`, then for each return the highest-version record that still
+/// satisfies that package's spec in the project manifest (if any).
+///
+/// This intentionally avoids a full re-solve: it only loads records for the
+/// packages already locked and their dependency closure, rather than every name
+/// in the channel index.
+///
+/// Returns a map from normalized package name to its highest-version record.
+/// Package names with no matching repodata are simply absent from the map.
+async fn fetch_latest_conda_records(
+    project: &Project,
+    platform: Platform,
+    locked_names: &[PackageName],
+    project_dependency_specs: &std::collections::HashMap<PackageName, NamelessMatchSpec>,
+) -> miette::Result<IndexMap<String, RepoDataRecord>> {
+    if locked_names.is_empty() {
+        return Ok(IndexMap::new());
+    }
+
+    let authenticated_client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+        .with_arc(Arc::new(AuthenticationMiddleware::default()))
+        .build();
+    let sparse_repodata =
+        repodata::fetch_sparse_repodata(project.channels(), [platform], &authenticated_client)
+            .await?;
+
+    // `load_records_recursive` also pulls in the transitive dependency closure of
+    // `locked_names`, same as it does when solving in `common.rs`; we only end up
+    // looking at the `locked_names` entries of `available_records` below, so the
+    // extra closure records are fetched but otherwise unused here.
+    let available_records = SparseRepoData::load_records_recursive(
+        sparse_repodata.values(),
+        locked_names.to_vec(),
+        None,
+    )
+    .into_diagnostic()?;
+
+    let mut latest_by_name: IndexMap<String, RepoDataRecord> = IndexMap::new();
+    for record in available_records {
+        let name = &record.package_record.name;
+        if !locked_names.contains(name) {
+            continue;
+        }
+
+        // Respect the project's own constraint on this package, if it has one,
+        // so `latest_version` reflects what could actually be installed here.
+        if let Some(nameless_spec) = project_dependency_specs.get(name) {
+            let spec = MatchSpec::from_nameless(nameless_spec.clone(), Some(name.clone()));
+            if !spec.matches(&record.package_record) {
+                continue;
+            }
+        }
+
+        let name = name.as_normalized().to_string();
+        match latest_by_name.get(&name) {
+            Some(existing) if existing.package_record.version >= record.package_record.version => {}
+            _ => {
+                latest_by_name.insert(name, record);
+            }
+        }
+    }
+
+    Ok(latest_by_name)
+}
+
+fn print_packages_as_table(packages: &Vec<PackageToOutput>, outdated: bool) -> io::Result<()> {
     let mut writer = tabwriter::TabWriter::new(stdout());
 
     let header_style = console::Style::new().bold();
-    writeln!(
+    write!(
         writer,
         "{}\t{}\t{}\t{}\t{}\t{}",
         header_style.apply_to("Package"),
@@ -217,6 +346,15 @@ fn print_packages_as_table(packages: &Vec<PackageToOutput>) -> io::Result<()> {
         header_style.apply_to("Kind"),
         header_style.apply_to("Source")
     )?;
+    if outdated {
+        write!(
+            writer,
+            "\t{}\t{}",
+            header_style.apply_to("Latest Version"),
+            header_style.apply_to("Latest Build")
+        )?;
+    }
+    writeln!(writer)?;
 
     for package in packages {
         if package.is_explicit {
@@ -235,7 +373,7 @@ fn print_packages_as_table(packages: &Vec<PackageToOutput>) -> io::Result<()> {
             .map(|size| human_bytes(size as f64))
             .unwrap_or_default();
 
-        writeln!(
+        write!(
             writer,
             "\t{}\t{}\t{}\t{}\t{}",
             &package.version,
@@ -244,6 +382,22 @@ fn print_packages_as_table(packages: &Vec<PackageToOutput>) -> io::Result<()> {
             &package.kind,
             package.source.as_deref().unwrap_or("")
         )?;
+
+        if outdated {
+            let latest_version = package.latest_version.as_deref().unwrap_or("");
+            let latest_build = package.latest_build.as_deref().unwrap_or("");
+            if package.is_outdated() {
+                write!(
+                    writer,
+                    "\t{}\t{}",
+                    console::style(latest_version).fg(Color::Red).bold(),
+                    latest_build
+                )?;
+            } else {
+                write!(writer, "\t{}\t{}", latest_version, latest_build)?;
+            }
+        }
+        writeln!(writer)?;
     }
 
     writer.flush()
@@ -264,6 +418,7 @@ fn create_package_to_output<'a, 'b>(
     p: &'b Package,
     project_dependency_names: &'a [String],
     registry_index: &'a mut RegistryWheelIndex<'b>,
+    latest_conda_records: &'a IndexMap<String, RepoDataRecord>,
 ) -> PackageToOutput {
     let name = p.name().to_string();
     let version = p.version().into_owned();
@@ -300,6 +455,15 @@ fn create_package_to_output<'a, 'b>(
 
     let is_explicit = project_dependency_names.contains(&name);
 
+    // `--outdated` only checks conda packages (see `Args::outdated`); pypi packages
+    // always get a blank latest-version column rather than a misleading guess.
+    let latest_record = match p {
+        Package::Conda(_) => latest_conda_records.get(&name),
+        Package::Pypi(_) => None,
+    };
+    let latest_version = latest_record.map(|r| r.package_record.version.to_string());
+    let latest_build = latest_record.map(|r| r.package_record.build.clone());
+
     PackageToOutput {
         name,
         version,
@@ -308,5 +472,7 @@ fn create_package_to_output<'a, 'b>(
         kind,
         source,
         is_explicit,
+        latest_version,
+        latest_build,
     }
 }
@@ -0,0 +1,30 @@
+use std::io::{self, Write};
+
+use clap::{Command, CommandFactory, Parser};
+use clap_mangen::Man;
+use miette::IntoDiagnostic;
+
+use crate::cli::Cli;
+
+/// Generate roff man pages for `pixi` and all of its subcommands, and print them to stdout.
+#[derive(Debug, Parser)]
+pub struct Args {}
+
+pub fn execute(_args: Args) -> miette::Result<()> {
+    let command = Cli::command();
+    let mut stdout = io::stdout();
+    render_man_page(&command, &mut stdout)
+}
+
+/// Render the man page for `command`, then recurse into its subcommands.
+fn render_man_page(command: &Command, writer: &mut impl Write) -> miette::Result<()> {
+    Man::new(command.clone())
+        .render(writer)
+        .into_diagnostic()?;
+
+    for subcommand in command.get_subcommands() {
+        render_man_page(subcommand, writer)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use clap::{Parser, Subcommand};
+
+pub mod completion;
+pub mod global;
+pub mod list;
+pub mod man;
+
+/// The `pixi` command line interface.
+#[derive(Debug, Parser)]
+#[clap(version, about, arg_required_else_help = true)]
+pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// List project's packages.
+    List(list::Args),
+
+    /// Subcommand for managing global tool installations.
+    Global {
+        #[clap(subcommand)]
+        command: GlobalCommands,
+    },
+
+    /// Generate shell completion scripts.
+    Completion(completion::Args),
+
+    /// Generate man pages.
+    Man(man::Args),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GlobalCommands {
+    /// Reconcile globally installed tools with `~/.pixi/global.toml`.
+    Sync(global::sync::Args),
+}
+
+/// Dispatch a parsed [`Cli`] to the appropriate subcommand implementation.
+pub async fn execute(cli: Cli) -> miette::Result<()> {
+    match cli.command {
+        Commands::List(args) => list::execute(args).await,
+        Commands::Global { command } => match command {
+            GlobalCommands::Sync(args) => global::sync::execute(args).await,
+        },
+        Commands::Completion(args) => completion::execute(args),
+        Commands::Man(args) => man::execute(args),
+    }
+}
+
+/// Shared flags for controlling whether a lock-file may be created or updated.
+#[derive(Debug, Default, Clone, Parser)]
+pub struct LockFileUsageArgs {
+    /// Don't update the lock-file, return an error if it is out of date.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Don't check if the lock-file is up to date, use it as-is.
+    #[arg(long, conflicts_with = "locked")]
+    pub frozen: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn completion_and_man_are_wired_up() {
+        let command = Cli::command();
+        let subcommand_names = command
+            .get_subcommands()
+            .map(|c| c.get_name())
+            .collect::<Vec<_>>();
+
+        assert!(subcommand_names.contains(&"completion"));
+        assert!(subcommand_names.contains(&"man"));
+    }
+}